@@ -0,0 +1,674 @@
+//! `patchwork` — command-line companion to the Rust drop-in.
+//!
+//! Like the benchmarks, this has no crate of its own. Copy it to the hosting
+//! crate's `src/bin/` directory, point the `#[path]` below at the drop-in and
+//! declare it with `required-features = ["cli"]`.
+//!
+//!   patchwork schema [--format json|ts|py] [--out FILE]
+//!   patchwork list [SOURCE] [--server NAME] [--gap-type TYPE]
+//!                  [--resolution RES] [--session ID] [--tag TAG] [--unreviewed]
+//!                  [--limit N] [--json]
+//!   patchwork show ID [SOURCE] [--json]
+//!   patchwork tail [SOURCE] [-n N] [-f] [--json]
+//!   patchwork export [SOURCE] [--format csv|jsonl|parquet] [--since DATE]
+//!                    [--out FILE]
+//!   patchwork dead-letter list FILE [--json]
+//!   patchwork dead-letter resubmit FILE [--url SIDECAR_URL]
+//!
+//! `schema` prints the tool definition and payload schema generated from
+//! `feedback_tool.rs`, for keeping the Python and TypeScript drop-ins and
+//! the sidecar in step with it.
+//!
+//! `list`, `show` and `tail` read what agents have reported. SOURCE is
+//! `--file PATH` for a `FileSink` file (its rotated files included),
+//! `--spool DIR` for a spool directory, or by default the sidecar at
+//! `FEEDBACK_SIDECAR_URL` (or `--url`), with `FEEDBACK_API_KEY`. Without a
+//! sidecar, `FEEDBACK_FILE_PATH` is read when set. Reports that never
+//! reached a sidecar are identified by their `client_id`. `tail -f` keeps
+//! printing new reports as they arrive.
+//!
+//! `export` writes every report (recorded on or after `--since`, a
+//! `YYYY-MM-DD` date or RFC 3339 time) oldest first, one flat row each:
+//! lists are joined with `; `, and tool and note counts get columns of their
+//! own. CSV and JSON lines go to stdout unless `--out` is given; Parquet
+//! needs `--out` and the `parquet` feature.
+//!
+//! `dead-letter` works on the file `Options::dead_letter` writes: `list`
+//! shows what the sidecar refused and why, and `resubmit` sends it all
+//! again, e.g. after fixing an API key, keeping whatever is refused again.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[path = "../feedback_tool.rs"]
+#[allow(dead_code, unused_imports)]
+mod feedback_tool;
+
+use feedback_tool::{
+    export_schema, read_feedback_lines, DeadLetters, FeedbackFilter, FeedbackReader,
+    FeedbackRecord, Options, SchemaFormat, Spool,
+};
+
+const USAGE: &str = "\
+usage: patchwork schema [--format json|ts|py] [--out FILE]
+       patchwork list [SOURCE] [--server NAME] [--gap-type TYPE] [--resolution RES]
+                      [--session ID] [--tag TAG] [--unreviewed] [--limit N] [--json]
+       patchwork show ID [SOURCE] [--json]
+       patchwork tail [SOURCE] [-n N] [-f] [--json]
+       patchwork export [SOURCE] [--format csv|jsonl|parquet] [--since DATE] [--out FILE]
+       patchwork dead-letter list FILE [--json]
+       patchwork dead-letter resubmit FILE [--url SIDECAR_URL]
+SOURCE: --file PATH | --spool DIR | --url SIDECAR_URL";
+
+/// How often `tail -f` looks for new reports.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("schema") => schema(&args[1..]),
+        Some(command @ ("list" | "show" | "tail" | "export")) => read(command, &args[1..]),
+        Some("dead-letter") => dead_letter(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn schema(args: &[String]) -> Result<(), String> {
+    let mut format = SchemaFormat::Json;
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = args.next().ok_or(USAGE)?.parse()?;
+            }
+            "--out" => out = Some(args.next().ok_or(USAGE)?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let text = export_schema(format);
+    match out {
+        Some(path) => std::fs::write(path, text).map_err(|e| format!("{path}: {e}")),
+        None => {
+            println!("{text}");
+            Ok(())
+        }
+    }
+}
+
+fn dead_letter(args: &[String]) -> Result<(), String> {
+    let (command, path) = match args {
+        [command, path, ..] if !path.starts_with('-') => (command.as_str(), path),
+        _ => return Err(USAGE.to_string()),
+    };
+    let mut json = false;
+    let mut url = None;
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--json" if command == "list" => json = true,
+            "--url" if command == "resubmit" => url = Some(rest.next().ok_or(USAGE)?.clone()),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let dead_letters = DeadLetters::open(path).map_err(|e| format!("{path}: {e}"))?;
+    match command {
+        "list" => {
+            for letter in dead_letters.entries().map_err(|e| format!("{path}: {e}"))? {
+                if json {
+                    println!("{}", serde_json::to_string(&letter).unwrap_or_default());
+                    continue;
+                }
+                let p = &letter.payload;
+                println!(
+                    "{}  {}  {}  {}  {}",
+                    letter.failed_at,
+                    letter.status,
+                    p.server_name,
+                    one_line(&p.what_i_needed, 60),
+                    one_line(&letter.body, 80)
+                );
+            }
+            Ok(())
+        }
+        "resubmit" => {
+            let opts = Options {
+                sidecar_url: url,
+                ..Options::default()
+            };
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            let dead_letters = std::sync::Arc::new(dead_letters);
+            let delivered = runtime
+                .block_on(dead_letters.resubmit(&opts))
+                .map_err(|e| format!("{path}: {e}"))?;
+            let left = dead_letters.entries().map_err(|e| format!("{path}: {e}"))?;
+            println!("{delivered} delivered, {} left", left.len());
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Where `list`, `show` and `tail` read reports from.
+enum Source {
+    File(PathBuf),
+    Spool(PathBuf),
+    Sidecar(Box<FeedbackReader>),
+}
+
+impl Source {
+    /// Reports matching `filter`, newest first.
+    async fn list(&self, filter: &FeedbackFilter) -> Result<Vec<FeedbackRecord>, String> {
+        let payloads = match self {
+            Source::Sidecar(reader) => return reader.list(filter).await.map_err(|e| e.to_string()),
+            Source::File(path) => {
+                let mut payloads = Vec::new();
+                for path in rotated_files(path) {
+                    payloads.extend(read_lines(&path)?);
+                }
+                payloads
+            }
+            Source::Spool(dir) => Spool::open(dir)
+                .and_then(|spool| spool.entries())
+                .map_err(|e| format!("{}: {e}", dir.display()))?,
+        };
+        let mut records: Vec<FeedbackRecord> = payloads
+            .into_iter()
+            .map(FeedbackRecord::from)
+            .filter(|r| matches(filter, r))
+            .collect();
+        records.reverse();
+        if let Some(limit) = filter.limit {
+            records.truncate(limit as usize);
+        }
+        Ok(records)
+    }
+
+    /// Every report matching `filter`, newest first.
+    async fn list_all(&self, filter: &FeedbackFilter) -> Result<Vec<FeedbackRecord>, String> {
+        match self {
+            Source::Sidecar(reader) => reader.list_all(filter).await.map_err(|e| e.to_string()),
+            _ => self.list(filter).await,
+        }
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<FeedbackRecord>, String> {
+        match self {
+            Source::Sidecar(reader) => reader.get(id).await.map_err(|e| e.to_string()),
+            _ => Ok(self
+                .list(&FeedbackFilter::default())
+                .await?
+                .into_iter()
+                .find(|r| r.id == id)),
+        }
+    }
+}
+
+/// `path`'s rotated files oldest first, then `path` itself.
+fn rotated_files(path: &std::path::Path) -> Vec<PathBuf> {
+    let rotated = |n: usize| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    };
+    let mut files: Vec<PathBuf> = (1..).map(rotated).take_while(|p| p.exists()).collect();
+    files.reverse();
+    files.push(path.to_path_buf());
+    files
+}
+
+fn read_lines(path: &std::path::Path) -> Result<Vec<feedback_tool::FeedbackPayload>, String> {
+    match read_feedback_lines(path) {
+        Ok(payloads) => Ok(payloads),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+/// [`FeedbackFilter`] as the sidecar applies it, for local sources.
+fn matches(filter: &FeedbackFilter, r: &FeedbackRecord) -> bool {
+    filter
+        .server_name
+        .as_ref()
+        .is_none_or(|v| *v == r.server_name)
+        && filter.gap_type.as_ref().is_none_or(|v| *v == r.gap_type)
+        && filter
+            .resolution
+            .as_ref()
+            .is_none_or(|v| *v == r.resolution)
+        && filter
+            .session_id
+            .as_ref()
+            .is_none_or(|v| *v == r.session_id)
+        && filter.tag.as_ref().is_none_or(|v| r.tags.contains(v))
+        && filter.reviewed.is_none_or(|v| v == r.reviewed)
+        && filter.since.as_ref().is_none_or(|v| r.timestamp >= *v)
+        && filter.before.as_ref().is_none_or(|v| r.timestamp < *v)
+}
+
+fn read(command: &str, args: &[String]) -> Result<(), String> {
+    let mut source = None;
+    let mut url = None;
+    let mut filter = FeedbackFilter::default();
+    let mut id = None;
+    let mut json = false;
+    let mut follow = false;
+    let mut count = 10;
+    let mut format = ExportFormat::Csv;
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or(USAGE.to_string());
+        match arg.as_str() {
+            "--file" => source = Some(Source::File(value()?.into())),
+            "--spool" => source = Some(Source::Spool(value()?.into())),
+            "--url" => url = Some(value()?),
+            "--json" => json = true,
+            "--server" if command == "list" => filter.server_name = Some(value()?),
+            "--gap-type" if command == "list" => filter.gap_type = Some(value()?.into()),
+            "--resolution" if command == "list" => filter.resolution = Some(value()?.into()),
+            "--session" if command == "list" => filter.session_id = Some(value()?),
+            "--tag" if command == "list" => filter.tag = Some(value()?),
+            "--unreviewed" if command == "list" => filter.reviewed = Some(false),
+            "--limit" if command == "list" => {
+                filter.limit = Some(value()?.parse().map_err(|_| USAGE.to_string())?)
+            }
+            "-n" if command == "tail" => count = value()?.parse().map_err(|_| USAGE.to_string())?,
+            "-f" | "--follow" if command == "tail" => follow = true,
+            "--format" if command == "export" => format = value()?.parse()?,
+            "--since" if command == "export" => filter.since = Some(parse_since(&value()?)?),
+            "--out" if command == "export" => out = Some(value()?),
+            other if command == "show" && id.is_none() && !other.starts_with('-') => {
+                id = Some(other.to_string())
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let source = match source {
+        Some(source) => source,
+        None => match (url, std::env::var("FEEDBACK_FILE_PATH")) {
+            (None, Ok(path)) if !path.is_empty() => Source::File(path.into()),
+            (url, _) => Source::Sidecar(Box::new(FeedbackReader::new(Options {
+                sidecar_url: url,
+                ..Options::default()
+            }))),
+        },
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    runtime.block_on(async {
+        match command {
+            "list" => {
+                for record in source.list(&filter).await? {
+                    print_summary(&record, json);
+                }
+                Ok(())
+            }
+            "show" => {
+                let id = id.ok_or(USAGE)?;
+                match source.get(&id).await? {
+                    Some(record) => {
+                        print_record(&record, json);
+                        Ok(())
+                    }
+                    None => Err(format!("no report with id {id}")),
+                }
+            }
+            "export" => {
+                let mut records = source.list_all(&filter).await?;
+                records.reverse();
+                export(&records, format, out.as_deref())
+            }
+            _ => tail(&source, count, follow, json).await,
+        }
+    })
+}
+
+/// Print the latest `count` reports oldest first, then, with `follow`,
+/// whatever arrives after them.
+async fn tail(source: &Source, count: u32, follow: bool, json: bool) -> Result<(), String> {
+    let filter = FeedbackFilter {
+        limit: Some(count.max(50)),
+        ..FeedbackFilter::default()
+    };
+    let mut seen = HashSet::new();
+    let mut first = true;
+    loop {
+        let records = source.list(&filter).await?;
+        let fresh: Vec<&FeedbackRecord> = records
+            .iter()
+            .filter(|r| !seen.contains(&tail_key(r)))
+            .collect();
+        let shown = if first { count as usize } else { fresh.len() };
+        for record in fresh.iter().take(shown).rev() {
+            print_summary(record, json);
+        }
+        seen.extend(records.iter().map(tail_key));
+        if !follow {
+            return Ok(());
+        }
+        first = false;
+        tokio::time::sleep(FOLLOW_INTERVAL).await;
+    }
+}
+
+/// What `tail` knows a report by: its id, or its content when it has none.
+fn tail_key(r: &FeedbackRecord) -> String {
+    if r.id.is_empty() {
+        format!("{}\0{}\0{}", r.server_name, r.session_id, r.what_i_needed)
+    } else {
+        r.id.clone()
+    }
+}
+
+fn print_summary(r: &FeedbackRecord, json: bool) {
+    if json {
+        println!("{}", serde_json::to_string(r).unwrap_or_default());
+        return;
+    }
+    let when = if r.timestamp.is_empty() {
+        "-"
+    } else {
+        &r.timestamp
+    };
+    let id = if r.id.is_empty() { "-" } else { &r.id };
+    println!(
+        "{when}  {id}  {}  {}  {}  {}",
+        r.server_name,
+        r.gap_type,
+        r.resolution,
+        one_line(&r.what_i_needed, 80)
+    );
+}
+
+fn print_record(r: &FeedbackRecord, json: bool) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(r).unwrap_or_default());
+        return;
+    }
+    let fields = [
+        ("id", r.id.as_str()),
+        ("timestamp", &r.timestamp),
+        ("server", &r.server_name),
+        ("gap type", &r.gap_type.to_string()),
+        ("resolution", &r.resolution.to_string()),
+        ("needed", &r.what_i_needed),
+        ("tried", &r.what_i_tried),
+        ("suggestion", &r.suggestion),
+        ("user goal", &r.user_goal),
+        ("agent model", &r.agent_model),
+        ("session", &r.session_id),
+        ("client type", &r.client_type),
+        ("tools", &r.tools_available.join(", ")),
+        ("tags", &r.tags.join(", ")),
+        ("follows up", &r.parent_id),
+        ("repeats", &r.duplicate_of),
+        ("thread", &r.thread_id),
+        ("pr", &r.pr_url),
+    ];
+    for (label, value) in fields {
+        if !value.is_empty() {
+            println!("{label:>12}: {value}");
+        }
+    }
+    println!("{:>12}: {}", "reviewed", r.reviewed);
+    for a in &r.attachments {
+        let mut label = format!("{} ({})", a.name, a.mime_type);
+        if a.truncated {
+            label.push_str(", truncated");
+        }
+        if a.base64 {
+            println!(
+                "\n  [attachment] {label}: {} bytes of base64",
+                a.content.len()
+            );
+        } else {
+            println!("\n  [attachment] {label}\n{}", a.content);
+        }
+    }
+    for note in &r.notes {
+        println!("\n  [{}] {}", note.timestamp, note.content);
+    }
+}
+
+/// `s` on one line, cut to `max` characters.
+fn one_line(s: &str, max: usize) -> String {
+    let flat = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.char_indices().nth(max) {
+        Some((cut, _)) => format!("{}…", &flat[..cut]),
+        None => flat,
+    }
+}
+
+/// `--since` as given, once it looks like a date or an RFC 3339 time.
+fn parse_since(value: &str) -> Result<String, String> {
+    let b = value.as_bytes();
+    let digits = |range: std::ops::Range<usize>| b[range].iter().all(u8::is_ascii_digit);
+    let date = b.len() >= 10 && digits(0..4) && b[4] == b'-' && digits(5..7) && b[7] == b'-';
+    if date && digits(8..10) && (b.len() == 10 || b[10] == b'T') {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "--since: expected YYYY-MM-DD or an RFC 3339 time, got {value}"
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ExportFormat {
+    Csv,
+    Jsonl,
+    Parquet,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "parquet" => Ok(ExportFormat::Parquet),
+            _ => Err(format!(
+                "unknown export format {s}; expected csv, jsonl or parquet"
+            )),
+        }
+    }
+}
+
+/// One flattened export column.
+enum Column {
+    Text(fn(&FeedbackRecord) -> String),
+    Count(fn(&FeedbackRecord) -> u64),
+    Flag(fn(&FeedbackRecord) -> bool),
+}
+
+const COLUMNS: &[(&str, Column)] = &[
+    ("id", Column::Text(|r| r.id.clone())),
+    ("timestamp", Column::Text(|r| r.timestamp.clone())),
+    ("server_name", Column::Text(|r| r.server_name.clone())),
+    ("gap_type", Column::Text(|r| r.gap_type.to_string())),
+    ("resolution", Column::Text(|r| r.resolution.to_string())),
+    ("what_i_needed", Column::Text(|r| r.what_i_needed.clone())),
+    ("what_i_tried", Column::Text(|r| r.what_i_tried.clone())),
+    ("suggestion", Column::Text(|r| r.suggestion.clone())),
+    ("user_goal", Column::Text(|r| r.user_goal.clone())),
+    ("agent_model", Column::Text(|r| r.agent_model.clone())),
+    ("session_id", Column::Text(|r| r.session_id.clone())),
+    ("client_type", Column::Text(|r| r.client_type.clone())),
+    ("server_version", Column::Text(|r| r.server_version.clone())),
+    ("deployment", Column::Text(|r| r.deployment.clone())),
+    ("workspace_id", Column::Text(|r| r.workspace_id.clone())),
+    ("created_at", Column::Text(|r| r.created_at.clone())),
+    ("seq", Column::Count(|r| r.seq)),
+    (
+        "metadata",
+        Column::Text(|r| {
+            if r.metadata.is_empty() {
+                String::new()
+            } else {
+                serde_json::Value::Object(r.metadata.clone()).to_string()
+            }
+        }),
+    ),
+    (
+        "tools_available",
+        Column::Text(|r| r.tools_available.join("; ")),
+    ),
+    ("tags", Column::Text(|r| r.tags.join("; "))),
+    ("duplicate_of", Column::Text(|r| r.duplicate_of.clone())),
+    ("parent_id", Column::Text(|r| r.parent_id.clone())),
+    ("thread_id", Column::Text(|r| r.thread_id.clone())),
+    (
+        "tool_count",
+        Column::Count(|r| r.tools_available.len() as u64),
+    ),
+    ("reviewed", Column::Flag(|r| r.reviewed)),
+    ("pr_url", Column::Text(|r| r.pr_url.clone())),
+    ("note_count", Column::Count(|r| r.notes.len() as u64)),
+    (
+        "attachment_count",
+        Column::Count(|r| r.attachments.len() as u64),
+    ),
+    (
+        "notes",
+        Column::Text(|r| {
+            r.notes
+                .iter()
+                .map(|n| n.content.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        }),
+    ),
+];
+
+impl Column {
+    fn json(&self, r: &FeedbackRecord) -> serde_json::Value {
+        match self {
+            Column::Text(f) => f(r).into(),
+            Column::Count(f) => f(r).into(),
+            Column::Flag(f) => f(r).into(),
+        }
+    }
+
+    fn text(&self, r: &FeedbackRecord) -> String {
+        match self {
+            Column::Text(f) => f(r),
+            Column::Count(f) => f(r).to_string(),
+            Column::Flag(f) => f(r).to_string(),
+        }
+    }
+}
+
+fn export(
+    records: &[FeedbackRecord],
+    format: ExportFormat,
+    out: Option<&str>,
+) -> Result<(), String> {
+    let text = match format {
+        ExportFormat::Csv => {
+            let mut text = csv_row(COLUMNS.iter().map(|(name, _)| name.to_string()));
+            for r in records {
+                text.push_str(&csv_row(COLUMNS.iter().map(|(_, column)| column.text(r))));
+            }
+            text
+        }
+        ExportFormat::Jsonl => {
+            let mut text = String::new();
+            for r in records {
+                let row: serde_json::Map<String, serde_json::Value> = COLUMNS
+                    .iter()
+                    .map(|(name, column)| (name.to_string(), column.json(r)))
+                    .collect();
+                text.push_str(&serde_json::Value::Object(row).to_string());
+                text.push('\n');
+            }
+            text
+        }
+        ExportFormat::Parquet => {
+            let path = out.ok_or("--format parquet needs --out FILE")?;
+            return write_parquet(records, path).map_err(|e| format!("{path}: {e}"));
+        }
+    };
+    match out {
+        Some(path) => std::fs::write(path, text).map_err(|e| format!("{path}: {e}")),
+        None => {
+            print!("{text}");
+            Ok(())
+        }
+    }
+}
+
+/// One RFC 4180 line: fields with commas, quotes or line breaks are quoted.
+fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let mut line = fields
+        .map(|f| {
+            if f.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(records: &[FeedbackRecord], path: &str) -> Result<(), String> {
+    use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    let mut fields = Vec::with_capacity(COLUMNS.len());
+    let mut arrays: Vec<ArrayRef> = Vec::with_capacity(COLUMNS.len());
+    for (name, column) in COLUMNS {
+        let (data_type, array): (DataType, ArrayRef) = match column {
+            Column::Text(f) => (
+                DataType::Utf8,
+                Arc::new(StringArray::from(records.iter().map(f).collect::<Vec<_>>())),
+            ),
+            Column::Count(f) => (
+                DataType::UInt64,
+                Arc::new(UInt64Array::from(records.iter().map(f).collect::<Vec<_>>())),
+            ),
+            Column::Flag(f) => (
+                DataType::Boolean,
+                Arc::new(BooleanArray::from(
+                    records.iter().map(f).collect::<Vec<_>>(),
+                )),
+            ),
+        };
+        fields.push(Field::new(*name, data_type, false));
+        arrays.push(array);
+    }
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(|e| e.to_string())?;
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut writer =
+        parquet::arrow::ArrowWriter::try_new(file, schema, None).map_err(|e| e.to_string())?;
+    writer.write(&batch).map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet(_records: &[FeedbackRecord], _path: &str) -> Result<(), String> {
+    Err("Parquet export needs the `parquet` feature".to_string())
+}
@@ -0,0 +1,172 @@
+//! `#[derive(PatchworkServer)]` — companion to the Rust drop-in.
+//!
+//! A derive has to live in a proc-macro crate, so unlike the drop-in this
+//! file can't just be copied into the server's own crate. Make a small crate
+//! for it next to the server, with no dependencies:
+//!
+//!   [package]
+//!   name = "patchwork-derive"
+//!   version = "0.1.0"
+//!   edition = "2021"
+//!
+//!   [lib]
+//!   proc-macro = true
+//!   path = "patchwork_derive.rs"
+//!
+//! then depend on it from the crate hosting `feedback_tool.rs` and enable the
+//! drop-in's `derive` feature:
+//!
+//!   patchwork-derive = { path = "patchwork-derive", optional = true }
+//!   derive = ["dep:patchwork-derive"]
+//!
+//! The derive implements `PatchworkServer`, taking the name and version from
+//! the deriving crate's Cargo metadata unless `#[patchwork(...)]` gives them.
+//! Like `patchwork_server!`, it expects `PatchworkServer` and
+//! `ServerIdentity` to be in scope:
+//!
+//! ```ignore
+//! use feedback_tool::{PatchworkServer, ServerIdentity};
+//!
+//! #[derive(PatchworkServer)]                     // CARGO_PKG_NAME / VERSION
+//! struct BillingServer;
+//!
+//! #[derive(PatchworkServer)]
+//! #[patchwork(name = "billing-mcp", version = "2.1.0")]
+//! struct BillingServerV2 {
+//!     db: Pool,
+//! }
+//! ```
+
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+
+#[proc_macro_derive(PatchworkServer, attributes(patchwork))]
+pub fn derive_patchwork_server(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(expanded) => expanded,
+        Err((span, message)) => compile_error(span, &message),
+    }
+}
+
+type Error = (Span, String);
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut name = "env!(\"CARGO_PKG_NAME\")".to_string();
+    let mut version = "env!(\"CARGO_PKG_VERSION\")".to_string();
+    let mut tokens = input.into_iter().peekable();
+    let mut ident = None;
+
+    while let Some(token) = tokens.next() {
+        match token {
+            // `#[patchwork(...)]`; other attributes and doc comments pass.
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                let Some(TokenTree::Group(attr)) = tokens.next() else {
+                    continue;
+                };
+                let mut inner = attr.stream().into_iter();
+                match (inner.next(), inner.next()) {
+                    (Some(TokenTree::Ident(path)), Some(TokenTree::Group(args)))
+                        if path.to_string() == "patchwork"
+                            && args.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        for (key, value) in parse_args(args.stream())? {
+                            match key.as_str() {
+                                "name" => name = value,
+                                "version" => version = value,
+                                _ => {
+                                    return Err((
+                                        args.span(),
+                                        format!(
+                                            "unknown `patchwork` attribute `{key}`; \
+                                             expected `name` or `version`"
+                                        ),
+                                    ))
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            TokenTree::Ident(kw) if matches!(kw.to_string().as_str(), "struct" | "enum") => {
+                match tokens.next() {
+                    Some(TokenTree::Ident(i)) => ident = Some(i),
+                    _ => return Err((kw.span(), "expected a type name".to_string())),
+                }
+                if let Some(TokenTree::Punct(p)) = tokens.peek() {
+                    if p.as_char() == '<' {
+                        return Err((
+                            p.span(),
+                            "`PatchworkServer` can't be derived for generic types; \
+                             implement it per concrete type with `patchwork_server!`"
+                                .to_string(),
+                        ));
+                    }
+                }
+                break;
+            }
+            TokenTree::Ident(kw) if kw.to_string() == "union" => {
+                return Err((
+                    kw.span(),
+                    "`PatchworkServer` needs a struct or enum".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let ident = ident.ok_or_else(|| {
+        (
+            Span::call_site(),
+            "`PatchworkServer` needs a struct or enum".to_string(),
+        )
+    })?;
+    format!(
+        "impl PatchworkServer for {ident} {{
+            fn server_identity() -> ServerIdentity {{
+                ServerIdentity {{ name: {name}, version: {version} }}
+            }}
+        }}"
+    )
+    .parse()
+    .map_err(|e| (ident.span(), format!("could not expand: {e}")))
+}
+
+/// `key = "value", ...` pairs, values kept as the string literals they are.
+fn parse_args(stream: TokenStream) -> Result<Vec<(String, String)>, Error> {
+    let mut pairs = Vec::new();
+    let mut tokens = stream.into_iter();
+    while let Some(token) = tokens.next() {
+        let TokenTree::Ident(key) = token else {
+            return Err((token.span(), "expected `name` or `version`".to_string()));
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+            _ => return Err((key.span(), format!("expected `{key} = \"...\"`"))),
+        }
+        match tokens.next() {
+            Some(TokenTree::Literal(lit)) if lit.to_string().starts_with('"') => {
+                pairs.push((key.to_string(), lit.to_string()));
+            }
+            _ => return Err((key.span(), format!("`{key}` takes a string literal"))),
+        }
+        match tokens.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+            Some(other) => return Err((other.span(), "expected `,`".to_string())),
+        }
+    }
+    Ok(pairs)
+}
+
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let expanded: TokenStream = format!("::core::compile_error!({message:?});")
+        .parse()
+        .unwrap_or_default();
+    expanded
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+/* PatchworkMCP C bindings. Built from feedback_tool.rs with the `ffi` feature. */
+
+#ifndef PATCHWORK_H
+#define PATCHWORK_H
+
+/* Generated by cbindgen; edit feedback_tool.rs and regenerate instead. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define PATCHWORK_OK 0
+
+/**
+ * A required argument was NULL, not UTF-8 or not valid JSON.
+ */
+#define PATCHWORK_ERR_INVALID -1
+
+/**
+ * The spool directory, or the runtime, could not be set up or read.
+ */
+#define PATCHWORK_ERR_IO -2
+
+/**
+ * A spool call before `patchwork_configure` gave a spool directory.
+ */
+#define PATCHWORK_ERR_NO_SPOOL -3
+
+/**
+ * The library panicked; nothing unwinds into the caller.
+ */
+#define PATCHWORK_ERR_PANIC -4
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Set the sidecar, API key and spool directory for every later call.
+ * NULL leaves a setting to the environment (`FEEDBACK_SIDECAR_URL`,
+ * `FEEDBACK_API_KEY`) or, for `spool_dir`, off. Returns
+ * `PATCHWORK_OK` or an error code.
+ *
+ * # Safety
+ * Each argument is NULL or a NUL-terminated string valid for the call.
+ */
+int patchwork_configure(const char *sidecar_url, const char *api_key, const char *spool_dir);
+
+/**
+ * Deliver one report. `json` is the feedback tool's arguments as a JSON
+ * object, plus an optional `"server_name"`. Returns the message for the
+ * agent, to be freed with `patchwork_string_free`, or NULL if `json`
+ * is NULL or not a JSON object.
+ *
+ * # Safety
+ * `json` is NULL or a NUL-terminated string valid for the call.
+ */
+char *patchwork_send_feedback_json(const char *json);
+
+/**
+ * Free a string this library returned. NULL is a no-op.
+ *
+ * # Safety
+ * `s` is NULL or came from this library and has not been freed.
+ */
+void patchwork_string_free(char *s);
+
+/**
+ * How many reports the configured spool holds, or an error code.
+ */
+int64_t patchwork_spool_pending(void);
+
+/**
+ * Replay the configured spool; returns how many reports were
+ * delivered, or an error code.
+ */
+int64_t patchwork_spool_drain(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PATCHWORK_H */
@@ -11,6 +11,9 @@ Run:
 Configure:
     FEEDBACK_DB_PATH  - default: ./feedback.db
     FEEDBACK_API_KEY  - optional shared secret (must match drop-in)
+    FEEDBACK_WORKSPACE_KEYS - optional "workspace=key,..." keys, each scoped
+                        to its own workspace
+    FEEDBACK_SIGNING_SECRET - optional; require HMAC-signed submissions
     FEEDBACK_PORT     - default: 8099 (only used with `uv run server.py`)
 """
 
@@ -19,22 +22,43 @@ import re
 import uuid
 import json
 import base64
+import hashlib
+import hmac
 import sqlite3
+import zlib
 from datetime import datetime, timezone
 from contextlib import asynccontextmanager, contextmanager
-from typing import Optional
+from typing import Any, Literal, Optional
 
 import httpx
-from fastapi import FastAPI, HTTPException, Header, Query
+from fastapi import FastAPI, HTTPException, Header, Query, Request, WebSocket, WebSocketDisconnect
 from fastapi.middleware.cors import CORSMiddleware
 from fastapi.responses import HTMLResponse, StreamingResponse
-from pydantic import BaseModel, Field
+from fastapi.routing import APIRoute
+from pydantic import BaseModel, Field, ValidationError, model_validator
 
 
 # ── Config ───────────────────────────────────────────────────────────────────
 
 DB_PATH = os.environ.get("FEEDBACK_DB_PATH", "feedback.db")
 API_KEY = os.environ.get("FEEDBACK_API_KEY", "")
+SIGNING_SECRET = os.environ.get("FEEDBACK_SIGNING_SECRET", "")
+
+
+def _workspace_keys(spec: str) -> dict[str, str]:
+    """Parse FEEDBACK_WORKSPACE_KEYS ("workspace=key,workspace=key")."""
+    keys = {}
+    for pair in filter(None, (p.strip() for p in spec.split(","))):
+        workspace, _, key = (part.strip() for part in pair.partition("="))
+        if not workspace or not key:
+            raise ValueError(
+                f"FEEDBACK_WORKSPACE_KEYS: expected workspace=key, got {pair!r}"
+            )
+        keys[workspace] = key
+    return keys
+
+
+WORKSPACE_KEYS = _workspace_keys(os.environ.get("FEEDBACK_WORKSPACE_KEYS", ""))
 
 
 # ── Database ─────────────────────────────────────────────────────────────────
@@ -114,6 +138,36 @@ def _migrate_db():
             conn.execute("ALTER TABLE feedback ADD COLUMN pr_url TEXT DEFAULT ''")
         if "client_type" not in cols:
             conn.execute("ALTER TABLE feedback ADD COLUMN client_type TEXT DEFAULT ''")
+        if "server_version" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN server_version TEXT DEFAULT ''")
+        if "deployment" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN deployment TEXT DEFAULT ''")
+        if "created_at" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN created_at TEXT DEFAULT ''")
+        if "seq" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN seq INTEGER DEFAULT 0")
+        if "metadata" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN metadata TEXT DEFAULT '{}'")
+        if "attachments" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN attachments TEXT DEFAULT '[]'")
+        if "idempotency_key" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN idempotency_key TEXT DEFAULT ''")
+        if "gap_status" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN gap_status TEXT DEFAULT ''")
+        if "workspace_id" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN workspace_id TEXT DEFAULT ''")
+        if "tags" not in cols:
+            conn.execute("ALTER TABLE feedback ADD COLUMN tags TEXT DEFAULT '[]'")
+        for column in ("duplicate_of", "parent_id", "thread_id"):
+            if column not in cols:
+                conn.execute(f"ALTER TABLE feedback ADD COLUMN {column} TEXT DEFAULT ''")
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_feedback_thread ON feedback(thread_id)")
+        # Idempotency keys are unique per workspace, as in the Rust sidecar.
+        conn.execute("DROP INDEX IF EXISTS idx_feedback_idempotency_key")
+        conn.execute("""
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_feedback_workspace_idempotency_key
+            ON feedback(workspace_id, idempotency_key) WHERE idempotency_key != ''
+        """)
 
 
 # ── App ──────────────────────────────────────────────────────────────────────
@@ -138,17 +192,244 @@ app.add_middleware(
 )
 
 
+# ── Request Encoding ─────────────────────────────────────────────────────────
+# Drop-ins may compress large submissions with an encoding listed in the
+# Accept-Encoding header of our responses. zstd needs the optional
+# `zstandard` package. Signatures cover the decoded body.
+
+try:
+    import zstandard
+except ImportError:
+    zstandard = None
+
+ACCEPTED_ENCODINGS = ("gzip", "zstd") if zstandard else ("gzip",)
+DECODE_ERRORS = (zlib.error, zstandard.ZstdError) if zstandard else (zlib.error,)
+MAX_DECODED_BYTES = 16 * 1024 * 1024
+
+
+def decode_body(body: bytes, encoding: str) -> bytes:
+    encoding = encoding.strip().lower()
+    if encoding in ("", "identity"):
+        return body
+    if encoding not in ACCEPTED_ENCODINGS:
+        raise HTTPException(
+            status_code=415,
+            detail=f"Unsupported Content-Encoding {encoding}",
+            headers={"Accept-Encoding": ", ".join(ACCEPTED_ENCODINGS)},
+        )
+    try:
+        if encoding == "gzip":
+            decoded = zlib.decompressobj(16 + zlib.MAX_WBITS).decompress(
+                body, MAX_DECODED_BYTES + 1
+            )
+        else:
+            reader = zstandard.ZstdDecompressor().stream_reader(body)
+            decoded = b""
+            while len(decoded) <= MAX_DECODED_BYTES:
+                chunk = reader.read(MAX_DECODED_BYTES + 1 - len(decoded))
+                if not chunk:
+                    break
+                decoded += chunk
+    except DECODE_ERRORS:
+        raise HTTPException(status_code=400, detail="Malformed compressed body")
+    if len(decoded) > MAX_DECODED_BYTES:
+        raise HTTPException(status_code=413, detail="Decoded body too large")
+    return decoded
+
+
+class DecodingRequest(Request):
+    async def body(self) -> bytes:
+        if not hasattr(self, "_decoded_body"):
+            self._decoded_body = decode_body(
+                await super().body(), self.headers.get("content-encoding", "")
+            )
+        return self._decoded_body
+
+
+class DecodingRoute(APIRoute):
+    def get_route_handler(self):
+        handler = super().get_route_handler()
+
+        async def decoding_handler(request: Request):
+            return await handler(DecodingRequest(request.scope, request.receive))
+
+        return decoding_handler
+
+
+app.router.route_class = DecodingRoute
+
+
+@app.middleware("http")
+async def advertise_encodings(request: Request, call_next):
+    response = await call_next(request)
+    response.headers["Accept-Encoding"] = ", ".join(ACCEPTED_ENCODINGS)
+    return response
+
+
+# ── Binary Batches ───────────────────────────────────────────────────────────
+# The Rust drop-in's compact batch body: a string dictionary, then items whose
+# fields point into it by index. Its `encode_binary_batch` documents the
+# layout. Field IDs this server doesn't know are skipped.
+
+BINARY_BATCH_CONTENT_TYPE = "application/vnd.patchwork.batch"
+BATCH_FORMATS = ("application/json", BINARY_BATCH_CONTENT_TYPE)
+BINARY_STRING_FIELDS = {
+    1: "server_name", 2: "what_i_needed", 3: "what_i_tried", 4: "gap_type",
+    5: "suggestion", 6: "user_goal", 7: "resolution", 8: "agent_model",
+    9: "session_id", 10: "client_type", 13: "client_id", 14: "duplicate_of",
+    16: "server_version", 17: "deployment", 18: "created_at",
+    22: "workspace_id", 24: "parent_id",
+}
+BINARY_LIST_FIELDS = {11: "tools_available", 23: "tags"}
+BINARY_SEQ, BINARY_METADATA, BINARY_ATTACHMENTS = 19, 20, 21
+
+
+def decode_binary_batch(body: bytes) -> list[dict]:
+    """The items of a binary batch, as dicts for FeedbackIn.
+
+    Raises ValueError if the body is malformed.
+    """
+    pos = 0
+
+    def take(n: int) -> bytes:
+        nonlocal pos
+        if pos + n > len(body):
+            raise ValueError("unexpected end of input")
+        pos += n
+        return body[pos - n:pos]
+
+    def varint() -> int:
+        n, shift = 0, 0
+        while True:
+            byte = take(1)[0]
+            n |= (byte & 0x7F) << shift
+            if byte < 0x80:
+                return n
+            shift += 7
+            if shift > 63:
+                raise ValueError("varint too long")
+
+    def string_ref() -> str:
+        i = varint()
+        if i >= len(strings):
+            raise ValueError(f"dictionary index {i} out of range")
+        return strings[i]
+
+    if take(4) != b"PWB1":
+        raise ValueError("bad magic")
+    strings = [take(varint()).decode("utf-8") for _ in range(varint())]
+    items = []
+    for _ in range(varint()):
+        item: dict[str, Any] = {}
+        for _ in range(varint()):
+            field, kind = take(1)[0], take(1)[0]
+            if kind == 0:
+                value = string_ref()
+                if field in BINARY_STRING_FIELDS:
+                    item[BINARY_STRING_FIELDS[field]] = value
+                elif field == BINARY_SEQ:
+                    item["seq"] = int(value)
+                elif field == BINARY_METADATA:
+                    item["metadata"] = json.loads(value)
+                elif field == BINARY_ATTACHMENTS:
+                    item["attachments"] = json.loads(value)
+            elif kind == 1:
+                values = [string_ref() for _ in range(varint())]
+                if field in BINARY_LIST_FIELDS:
+                    item[BINARY_LIST_FIELDS[field]] = values
+            else:
+                raise ValueError(f"unknown value kind {kind}")
+        items.append(item)
+    return items
+
+
+@app.middleware("http")
+async def advertise_batch_formats(request: Request, call_next):
+    response = await call_next(request)
+    response.headers["X-Patchwork-Batch-Formats"] = ", ".join(BATCH_FORMATS)
+    return response
+
+
 # ── Auth ─────────────────────────────────────────────────────────────────────
 
-def check_auth(authorization: Optional[str] = Header(None)):
-    if not API_KEY:
+def _bearer_matches(authorization: Optional[str], key: str) -> bool:
+    return hmac.compare_digest((authorization or "").encode(), f"Bearer {key}".encode())
+
+
+def check_auth(authorization: Optional[str] = Header(None)) -> Optional[str]:
+    """Check a submission's API key.
+
+    FEEDBACK_API_KEY is the operator's key and may submit to any workspace;
+    a key from FEEDBACK_WORKSPACE_KEYS only to its own. Returns the
+    workspace the key is bound to, or None when it isn't bound to one.
+    """
+    if not API_KEY and not WORKSPACE_KEYS:
+        return None
+    if API_KEY and _bearer_matches(authorization, API_KEY):
+        return None
+    for workspace, key in WORKSPACE_KEYS.items():
+        if _bearer_matches(authorization, key):
+            return workspace
+    raise HTTPException(status_code=401, detail="Invalid API key")
+
+
+async def check_signature(request: Request):
+    """Verify X-Patchwork-Signature (sha256=<hex HMAC of the raw body>)."""
+    if not SIGNING_SECRET:
         return
-    if not authorization or authorization != f"Bearer {API_KEY}":
-        raise HTTPException(status_code=401, detail="Invalid API key")
+    body = await request.body()
+    expected = "sha256=" + hmac.new(SIGNING_SECRET.encode(), body, hashlib.sha256).hexdigest()
+    given = request.headers.get("x-patchwork-signature", "").strip()
+    if not hmac.compare_digest(given, expected):
+        raise HTTPException(status_code=401, detail="Invalid signature")
+
+
+# Payload schema versions this server understands. Drop-ins send theirs in
+# X-Patchwork-Schema-Version and step down to one listed here on a 426.
+SUPPORTED_SCHEMA_VERSIONS = (1, 2, 3, 4, 5, 6, 7, 8, 9, 10)
+
+
+def check_schema_version(version: Optional[str]):
+    if version is None:
+        return
+    if not version.strip().isdigit() or int(version) not in SUPPORTED_SCHEMA_VERSIONS:
+        raise HTTPException(
+            status_code=426,
+            detail={
+                "error": f"Unsupported schema version {version}",
+                "supported_versions": list(SUPPORTED_SCHEMA_VERSIONS),
+            },
+            headers={
+                "X-Patchwork-Schema-Versions": ", ".join(
+                    str(v) for v in SUPPORTED_SCHEMA_VERSIONS
+                )
+            },
+        )
 
 
 # ── Models ───────────────────────────────────────────────────────────────────
 
+# Limits on attachments, matching the drop-ins: content beyond
+# MAX_ATTACHMENT_BYTES (before base64) is refused.
+MAX_ATTACHMENTS = 8
+MAX_ATTACHMENT_BYTES = 64 * 1024
+
+
+class Attachment(BaseModel):
+    name: str
+    mime_type: str = ""
+    content: str = ""
+    base64: bool = False
+    truncated: bool = False
+
+    @model_validator(mode="after")
+    def check_size(self):
+        limit = -(-MAX_ATTACHMENT_BYTES // 3) * 4 if self.base64 else MAX_ATTACHMENT_BYTES
+        if len(self.content.encode()) > limit:
+            raise ValueError(f"Attachment {self.name!r} exceeds {MAX_ATTACHMENT_BYTES} bytes")
+        return self
+
+
 class FeedbackIn(BaseModel):
     server_name: str = "unknown"
     what_i_needed: str
@@ -161,10 +442,22 @@ class FeedbackIn(BaseModel):
     tools_available: list[str] = Field(default_factory=list)
     session_id: str = ""
     client_type: str = ""
+    server_version: str = ""
+    deployment: str = ""
+    workspace_id: str = ""
+    created_at: str = ""
+    seq: int = 0
+    metadata: dict[str, Any] = Field(default_factory=dict)
+    attachments: list[Attachment] = Field(default_factory=list, max_length=MAX_ATTACHMENTS)
+    tags: list[str] = Field(default_factory=list)
+    duplicate_of: str = ""
+    parent_id: str = ""
+    client_id: str = ""
 
 
 class ReviewUpdate(BaseModel):
-    reviewed: bool = True
+    reviewed: Optional[bool] = None
+    gap_status: Optional[Literal["new", "acknowledged", "planned", "shipped"]] = None
 
 
 class NoteIn(BaseModel):
@@ -177,7 +470,31 @@ def _row_to_dict(row: sqlite3.Row) -> dict:
     d = dict(row)
     d["reviewed"] = bool(d["reviewed"])
     d.setdefault("pr_url", "")
+    d.setdefault("gap_status", "")
     d.setdefault("client_type", "")
+    d.setdefault("server_version", "")
+    d.setdefault("deployment", "")
+    d.setdefault("workspace_id", "")
+    d.setdefault("created_at", "")
+    d.setdefault("duplicate_of", "")
+    d.setdefault("parent_id", "")
+    d.setdefault("thread_id", "")
+    d["seq"] = d.get("seq") or 0
+    try:
+        metadata = json.loads(d.get("metadata") or "{}")
+    except (json.JSONDecodeError, TypeError):
+        metadata = {}
+    d["metadata"] = metadata if isinstance(metadata, dict) else {}
+    try:
+        attachments = json.loads(d.get("attachments") or "[]")
+    except (json.JSONDecodeError, TypeError):
+        attachments = []
+    d["attachments"] = attachments if isinstance(attachments, list) else []
+    try:
+        tags = json.loads(d.get("tags") or "[]")
+    except (json.JSONDecodeError, TypeError):
+        tags = []
+    d["tags"] = tags if isinstance(tags, list) else []
     if "tools_available" in d:
         try:
             d["tools_available"] = json.loads(d["tools_available"])
@@ -618,45 +935,294 @@ def _validate_llm_result(result: dict) -> dict:
 
 # ── Routes ───────────────────────────────────────────────────────────────────
 
+def _linked(conn, link: str, workspace_id: str) -> Optional[tuple[str, str]]:
+    """The report `link` names in the workspace, by id or by the idempotency
+    key it was stored under, as (id, thread_id)."""
+    if not link:
+        return None
+    row = conn.execute(
+        "SELECT id, thread_id FROM feedback"
+        " WHERE (id = ? OR idempotency_key = ?) AND workspace_id = ?"
+        " ORDER BY id = ? DESC LIMIT 1",
+        (link, link, workspace_id, link),
+    ).fetchone()
+    if not row:
+        return None
+    # Rows from before threading are their own thread.
+    return row[0], row[1] or row[0]
+
+
+class WorkspaceMismatch(ValueError):
+    """A payload's workspace_id disagrees with its request's workspace."""
+
+
+def _workspace(header: Optional[str], bound: Optional[str] = None) -> str:
+    """The workspace a request is scoped to; empty for the default one.
+
+    A request made with a workspace's own key (`bound`, from check_auth) is
+    scoped to that workspace, and naming another one is refused.
+    """
+    requested = (header or "").strip()
+    if bound is None:
+        return requested
+    if header is not None and requested != bound:
+        raise HTTPException(status_code=403, detail="API key is not valid for this workspace")
+    return bound
+
+
+def _review_workspace(header: Optional[str], authorization: Optional[str]) -> str:
+    """The workspace a review request is scoped to. A workspace listed in
+    FEEDBACK_WORKSPACE_KEYS is only readable with its own key or the
+    operator's."""
+    workspace = _workspace(header)
+    key = WORKSPACE_KEYS.get(workspace)
+    if key is not None and not (
+        _bearer_matches(authorization, key)
+        or (API_KEY and _bearer_matches(authorization, API_KEY))
+    ):
+        raise HTTPException(status_code=401, detail="Invalid API key")
+    return workspace
+
+
+def insert_feedback(
+    conn,
+    feedback: FeedbackIn,
+    idempotency_key: Optional[str] = None,
+    workspace: Optional[str] = None,
+) -> str:
+    """Store one feedback item and return its new id.
+
+    A retried or replayed submission carries the same idempotency key (the
+    Idempotency-Key header, else the payload's client_id); it is stored once
+    per workspace and gets the id assigned the first time. The
+    X-Patchwork-Workspace header, when sent, files the item under that
+    workspace; a payload naming a different workspace_id raises
+    WorkspaceMismatch.
+
+    An item whose parent_id or duplicate_of names an earlier one, by id or
+    by its key, joins that item's thread. Items that named this one by key
+    before it arrived are moved into its thread now.
+    """
+    workspace_id = _workspace(workspace) or feedback.workspace_id
+    if feedback.workspace_id and feedback.workspace_id != workspace_id:
+        raise WorkspaceMismatch(
+            "`workspace_id` does not match the X-Patchwork-Workspace header"
+        )
+    key = idempotency_key or feedback.client_id
+    if key:
+        existing = conn.execute(
+            "SELECT id FROM feedback WHERE idempotency_key = ? AND workspace_id = ?",
+            (key, workspace_id),
+        ).fetchone()
+        if existing:
+            return existing[0]
+    row_id = str(uuid.uuid4())
+    now = datetime.now(timezone.utc).isoformat()
+    # Agents quote ids the way they were shown them.
+    parent_id = feedback.parent_id.strip().removeprefix("FB-")
+    parent = _linked(conn, parent_id, workspace_id)
+    duplicate = _linked(conn, feedback.duplicate_of, workspace_id)
+    thread_id = (parent or duplicate or (row_id, row_id))[1]
+    conn.execute(
+        """
+        INSERT INTO feedback
+            (id, server_name, timestamp, what_i_needed, what_i_tried,
+             gap_type, suggestion, user_goal, resolution, agent_model,
+             tools_available, session_id, client_type, server_version,
+             deployment, created_at, seq, metadata, attachments, idempotency_key,
+             workspace_id, tags, duplicate_of, parent_id, thread_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        """,
+        (
+            row_id,
+            feedback.server_name,
+            now,
+            feedback.what_i_needed,
+            feedback.what_i_tried,
+            feedback.gap_type,
+            feedback.suggestion,
+            feedback.user_goal,
+            feedback.resolution,
+            feedback.agent_model,
+            json.dumps(feedback.tools_available),
+            feedback.session_id,
+            feedback.client_type,
+            feedback.server_version,
+            feedback.deployment,
+            feedback.created_at,
+            feedback.seq,
+            json.dumps(feedback.metadata),
+            json.dumps([a.model_dump() for a in feedback.attachments]),
+            key,
+            workspace_id,
+            json.dumps(feedback.tags),
+            duplicate[0] if duplicate else feedback.duplicate_of,
+            parent[0] if parent else parent_id,
+            thread_id,
+        ),
+    )
+    if key:
+        conn.execute(
+            "UPDATE feedback SET thread_id = ? WHERE workspace_id = ? AND thread_id IN ("
+            " SELECT id FROM feedback WHERE workspace_id = ?"
+            " AND (duplicate_of = ? OR parent_id = ?))",
+            (thread_id, workspace_id, workspace_id, key, key),
+        )
+        conn.execute(
+            "UPDATE feedback"
+            " SET duplicate_of = CASE WHEN duplicate_of = ? THEN ? ELSE duplicate_of END,"
+            " parent_id = CASE WHEN parent_id = ? THEN ? ELSE parent_id END"
+            " WHERE workspace_id = ? AND (duplicate_of = ? OR parent_id = ?)",
+            (key, row_id, key, row_id, workspace_id, key, key),
+        )
+    return row_id
+
+
 @app.post("/api/feedback", status_code=201)
 async def create_feedback(
     feedback: FeedbackIn,
+    request: Request,
     authorization: Optional[str] = Header(None),
+    x_patchwork_schema_version: Optional[str] = Header(None),
+    idempotency_key: Optional[str] = Header(None),
+    x_patchwork_workspace: Optional[str] = Header(None),
 ):
-    check_auth(authorization)
-
-    row_id = str(uuid.uuid4())
-    now = datetime.now(timezone.utc).isoformat()
+    workspace = _workspace(x_patchwork_workspace, check_auth(authorization))
+    await check_signature(request)
+    check_schema_version(x_patchwork_schema_version)
 
     with get_db() as conn:
-        conn.execute(
-            """
-            INSERT INTO feedback
-                (id, server_name, timestamp, what_i_needed, what_i_tried,
-                 gap_type, suggestion, user_goal, resolution, agent_model,
-                 tools_available, session_id, client_type)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            """,
-            (
-                row_id,
-                feedback.server_name,
-                now,
-                feedback.what_i_needed,
-                feedback.what_i_tried,
-                feedback.gap_type,
-                feedback.suggestion,
-                feedback.user_goal,
-                feedback.resolution,
-                feedback.agent_model,
-                json.dumps(feedback.tools_available),
-                feedback.session_id,
-                feedback.client_type,
-            ),
-        )
+        try:
+            row_id = insert_feedback(conn, feedback, idempotency_key, workspace)
+        except WorkspaceMismatch as e:
+            raise HTTPException(status_code=403, detail=str(e))
 
     return {"id": row_id, "status": "recorded"}
 
 
+MAX_BATCH_ITEMS = 500
+
+
+@app.post("/api/feedback/batch", status_code=201)
+async def create_feedback_batch(
+    request: Request,
+    authorization: Optional[str] = Header(None),
+    x_patchwork_schema_version: Optional[str] = Header(None),
+    x_patchwork_workspace: Optional[str] = Header(None),
+):
+    """Store an array of feedback items in one request, sent as JSON or as a
+    binary batch (see decode_binary_batch).
+
+    Items are validated one by one, so a bad item doesn't sink the batch:
+    the response has one result per item, in order, either
+    {"id": ..., "status": "recorded"} or {"status": "invalid", "error": ...}.
+    """
+    workspace = _workspace(x_patchwork_workspace, check_auth(authorization))
+    await check_signature(request)
+    check_schema_version(x_patchwork_schema_version)
+
+    content_type = request.headers.get("content-type", "")
+    if content_type.startswith(BINARY_BATCH_CONTENT_TYPE):
+        try:
+            items = decode_binary_batch(await request.body())
+        except ValueError as e:
+            raise HTTPException(status_code=400, detail=f"Invalid binary batch: {e}")
+    elif content_type.startswith("application/json"):
+        try:
+            items = await request.json()
+        except ValueError:
+            raise HTTPException(status_code=400, detail="Invalid JSON")
+    else:
+        raise HTTPException(
+            status_code=415,
+            detail=f"Batches must be sent as one of: {', '.join(BATCH_FORMATS)}",
+        )
+    if not isinstance(items, list):
+        raise HTTPException(status_code=422, detail="Expected a JSON array of feedback items")
+    if len(items) > MAX_BATCH_ITEMS:
+        raise HTTPException(
+            status_code=413, detail=f"At most {MAX_BATCH_ITEMS} items per batch"
+        )
+
+    results = []
+    with get_db() as conn:
+        for item in items:
+            try:
+                feedback = FeedbackIn.model_validate(item)
+            except ValidationError as e:
+                results.append({"status": "invalid", "error": str(e)})
+                continue
+            try:
+                row_id = insert_feedback(conn, feedback, workspace=workspace)
+            except WorkspaceMismatch as e:
+                results.append({"status": "invalid", "error": str(e)})
+                continue
+            results.append({"id": row_id, "status": "recorded"})
+
+    return {"results": results}
+
+
+def stream_ack(frame_text: str, workspace: str = "") -> dict:
+    """Store the feedback in one WebSocket frame, in the connection's
+    workspace, and build its ack."""
+    try:
+        frame = json.loads(frame_text)
+    except ValueError:
+        frame = None
+    if not isinstance(frame, dict):
+        frame = {}
+    ref = frame.get("ref")
+
+    def refuse(code: int, error: str) -> dict:
+        return {"ref": ref, "status": "invalid", "code": code, "error": error}
+
+    body = frame.get("body")
+    if not isinstance(body, str):
+        return refuse(400, "Expected a frame with a `body` string")
+    if SIGNING_SECRET:
+        expected = "sha256=" + hmac.new(
+            SIGNING_SECRET.encode(), body.encode(), hashlib.sha256
+        ).hexdigest()
+        if not hmac.compare_digest(str(frame.get("signature", "")).strip(), expected):
+            return refuse(401, "Invalid signature")
+    try:
+        feedback = FeedbackIn.model_validate_json(body)
+    except ValidationError as e:
+        return refuse(422, str(e))
+    with get_db() as conn:
+        try:
+            row_id = insert_feedback(conn, feedback, workspace=workspace)
+        except WorkspaceMismatch as e:
+            return refuse(403, str(e))
+    return {"ref": ref, "id": row_id, "status": "recorded"}
+
+
+@app.websocket("/api/feedback/ws")
+async def feedback_stream(websocket: WebSocket):
+    """Stream feedback over one connection instead of a POST per report.
+
+    Each text frame is {"ref": n, "body": "<payload JSON>", "signature": ...}
+    and is answered with {"ref": n, "id": ..., "status": "recorded"} or
+    {"ref": n, "status": "invalid", "code": ..., "error": ...}. The API key
+    and schema version are checked on the handshake, signatures per frame.
+    """
+    try:
+        workspace = _workspace(
+            websocket.headers.get("x-patchwork-workspace"),
+            check_auth(websocket.headers.get("authorization")),
+        )
+        check_schema_version(websocket.headers.get("x-patchwork-schema-version"))
+    except HTTPException:
+        await websocket.close(code=1008)
+        return
+    await websocket.accept()
+    try:
+        while True:
+            await websocket.send_json(stream_ack(await websocket.receive_text(), workspace))
+    except WebSocketDisconnect:
+        pass
+
+
 @app.get("/api/feedback")
 async def list_feedback(
     server_name: Optional[str] = Query(None),
@@ -664,11 +1230,16 @@ async def list_feedback(
     reviewed: Optional[bool] = Query(None),
     resolution: Optional[str] = Query(None),
     session_id: Optional[str] = Query(None),
+    tag: Optional[str] = Query(None),
+    since: Optional[str] = Query(None),
+    before: Optional[str] = Query(None),
     limit: int = Query(50, le=200),
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
 ):
     with get_db() as conn:
-        query = "SELECT * FROM feedback WHERE 1=1"
-        params: list = []
+        query = "SELECT * FROM feedback WHERE workspace_id = ?"
+        params: list = [_review_workspace(x_patchwork_workspace, authorization)]
 
         if server_name:
             query += " AND server_name = ?"
@@ -685,6 +1256,15 @@ async def list_feedback(
         if session_id:
             query += " AND session_id = ?"
             params.append(session_id)
+        if tag:
+            query += " AND EXISTS (SELECT 1 FROM json_each(feedback.tags) WHERE value = ?)"
+            params.append(tag)
+        if since:
+            query += " AND timestamp >= ?"
+            params.append(since)
+        if before:
+            query += " AND timestamp < ?"
+            params.append(before)
 
         query += " ORDER BY timestamp DESC LIMIT ?"
         params.append(limit)
@@ -695,10 +1275,15 @@ async def list_feedback(
 
 
 @app.get("/api/feedback/{feedback_id}")
-async def get_feedback(feedback_id: str):
+async def get_feedback(
+    feedback_id: str,
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
+):
     with get_db() as conn:
         row = conn.execute(
-            "SELECT * FROM feedback WHERE id = ?", (feedback_id,)
+            "SELECT * FROM feedback WHERE id = ? AND workspace_id = ?",
+            (feedback_id, _review_workspace(x_patchwork_workspace, authorization)),
         ).fetchone()
         if not row:
             raise HTTPException(status_code=404, detail="Not found")
@@ -706,12 +1291,52 @@ async def get_feedback(feedback_id: str):
         return items[0]
 
 
+@app.get("/api/feedback/{feedback_id}/thread")
+async def get_feedback_thread(
+    feedback_id: str,
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
+):
+    """Every item in the same thread as this one, oldest first: the root,
+    the follow-ups and repeats linked to it, and theirs in turn."""
+    workspace = _review_workspace(x_patchwork_workspace, authorization)
+    with get_db() as conn:
+        row = conn.execute(
+            "SELECT thread_id FROM feedback WHERE id = ? AND workspace_id = ?",
+            (feedback_id, workspace),
+        ).fetchone()
+        if not row:
+            raise HTTPException(status_code=404, detail="Not found")
+        root = row[0] or feedback_id
+        rows = conn.execute(
+            "SELECT * FROM feedback WHERE workspace_id = ? AND (thread_id = ? OR id = ?)"
+            " ORDER BY COALESCE(NULLIF(created_at, ''), timestamp) ASC, seq ASC",
+            (workspace, root, root),
+        ).fetchall()
+        return _attach_notes(conn, [_row_to_dict(r) for r in rows])
+
+
 @app.patch("/api/feedback/{feedback_id}")
-async def update_feedback(feedback_id: str, update: ReviewUpdate):
+async def update_feedback(
+    feedback_id: str,
+    update: ReviewUpdate,
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
+):
     with get_db() as conn:
+        # A bare {} marks the item reviewed, as it always has.
+        reviewed = update.reviewed
+        if reviewed is None and update.gap_status is None:
+            reviewed = True
         result = conn.execute(
-            "UPDATE feedback SET reviewed = ? WHERE id = ?",
-            (int(update.reviewed), feedback_id),
+            "UPDATE feedback SET reviewed = COALESCE(?, reviewed),"
+            " gap_status = COALESCE(?, gap_status) WHERE id = ? AND workspace_id = ?",
+            (
+                None if reviewed is None else int(reviewed),
+                update.gap_status,
+                feedback_id,
+                _review_workspace(x_patchwork_workspace, authorization),
+            ),
         )
         if result.rowcount == 0:
             raise HTTPException(status_code=404, detail="Not found")
@@ -719,10 +1344,16 @@ async def update_feedback(feedback_id: str, update: ReviewUpdate):
 
 
 @app.post("/api/feedback/{feedback_id}/notes", status_code=201)
-async def add_note(feedback_id: str, note: NoteIn):
+async def add_note(
+    feedback_id: str,
+    note: NoteIn,
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
+):
     with get_db() as conn:
         exists = conn.execute(
-            "SELECT 1 FROM feedback WHERE id = ?", (feedback_id,)
+            "SELECT 1 FROM feedback WHERE id = ? AND workspace_id = ?",
+            (feedback_id, _review_workspace(x_patchwork_workspace, authorization)),
         ).fetchone()
         if not exists:
             raise HTTPException(status_code=404, detail="Feedback item not found")
@@ -795,10 +1426,18 @@ def _sse_json(event: str, data: dict) -> str:
 
 
 @app.post("/api/feedback/{feedback_id}/draft-pr")
-async def draft_pr(feedback_id: str, force: bool = Query(False)):
+async def draft_pr(
+    feedback_id: str,
+    force: bool = Query(False),
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
+):
+    workspace = _review_workspace(x_patchwork_workspace, authorization)
     # Pre-validate before starting the stream
     with get_db() as conn:
-        row = conn.execute("SELECT * FROM feedback WHERE id = ?", (feedback_id,)).fetchone()
+        row = conn.execute(
+            "SELECT * FROM feedback WHERE id = ? AND workspace_id = ?", (feedback_id, workspace)
+        ).fetchone()
         if not row:
             raise HTTPException(404, "Feedback not found")
         fb = _row_to_dict(row)
@@ -894,7 +1533,10 @@ async def draft_pr(feedback_id: str, force: bool = Query(False)):
             )
 
             with get_db() as conn:
-                conn.execute("UPDATE feedback SET pr_url = ? WHERE id = ?", (pr_url, feedback_id))
+                conn.execute(
+                    "UPDATE feedback SET pr_url = ? WHERE id = ? AND workspace_id = ?",
+                    (pr_url, feedback_id, workspace),
+                )
 
             yield _sse_json("done", {"pr_url": pr_url, "branch": branch_name})
 
@@ -911,30 +1553,40 @@ async def draft_pr(feedback_id: str, force: bool = Query(False)):
 
 
 @app.get("/api/stats")
-async def stats():
+async def stats(
+    x_patchwork_workspace: Optional[str] = Header(None),
+    authorization: Optional[str] = Header(None),
+):
+    workspace = (_review_workspace(x_patchwork_workspace, authorization),)
     with get_db() as conn:
-        total = conn.execute("SELECT COUNT(*) as c FROM feedback").fetchone()["c"]
+        total = conn.execute(
+            "SELECT COUNT(*) as c FROM feedback WHERE workspace_id = ?", workspace
+        ).fetchone()["c"]
         unreviewed = conn.execute(
-            "SELECT COUNT(*) as c FROM feedback WHERE reviewed = 0"
+            "SELECT COUNT(*) as c FROM feedback WHERE workspace_id = ? AND reviewed = 0",
+            workspace,
         ).fetchone()["c"]
         note_count = conn.execute(
-            "SELECT COUNT(*) as c FROM feedback_notes"
+            "SELECT COUNT(*) as c FROM feedback_notes n"
+            " JOIN feedback f ON f.id = n.feedback_id WHERE f.workspace_id = ?",
+            workspace,
         ).fetchone()["c"]
 
         by_server = conn.execute("""
             SELECT server_name, COUNT(*) as count
-            FROM feedback GROUP BY server_name ORDER BY count DESC
-        """).fetchall()
+            FROM feedback WHERE workspace_id = ? GROUP BY server_name ORDER BY count DESC
+        """, workspace).fetchall()
 
         by_type = conn.execute("""
             SELECT gap_type, COUNT(*) as count
-            FROM feedback GROUP BY gap_type ORDER BY count DESC
-        """).fetchall()
+            FROM feedback WHERE workspace_id = ? GROUP BY gap_type ORDER BY count DESC
+        """, workspace).fetchall()
 
         by_resolution = conn.execute("""
             SELECT resolution, COUNT(*) as count
-            FROM feedback WHERE resolution != '' GROUP BY resolution ORDER BY count DESC
-        """).fetchall()
+            FROM feedback WHERE workspace_id = ? AND resolution != ''
+            GROUP BY resolution ORDER BY count DESC
+        """, workspace).fetchall()
 
     return {
         "total": total,